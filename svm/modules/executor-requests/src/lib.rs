@@ -1,7 +1,16 @@
+mod reader;
+pub mod relay_instructions;
+pub mod request_for_execution;
+pub mod signed_quote;
+
+use reader::Reader;
+
 const REQ_VAA_V1: &[u8; 4] = b"ERV1";
 const REQ_NTT_V1: &[u8; 4] = b"ERN1";
+const REQ_NTT_V2: &[u8; 4] = b"ERN2";
 const REQ_CCTP_V1: &[u8; 4] = b"ERC1";
 const REQ_CCTP_V2: &[u8; 4] = b"ERC2";
+const REQ_HASH_V1: &[u8; 4] = b"ERH1";
 
 /// Encodes a version 1 VAA request payload.
 pub fn make_vaa_v1_request(chain: u16, address: [u8; 32], sequence: u64) -> Vec<u8> {
@@ -18,6 +27,11 @@ pub fn make_vaa_v1_request(chain: u16, address: [u8; 32], sequence: u64) -> Vec<
     out
 }
 
+/// Encodes a version 1 VAA request payload for a 20-byte EVM emitter address.
+pub fn make_vaa_v1_request_evm(chain: u16, evm_addr: [u8; 20], sequence: u64) -> Vec<u8> {
+    make_vaa_v1_request(chain, left_pad_evm_address(evm_addr), sequence)
+}
+
 /// Encodes a version 1 NTT request payload.
 pub fn make_ntt_v1_request(
     source_chain: u16,
@@ -37,6 +51,41 @@ pub fn make_ntt_v1_request(
     out
 }
 
+/// Encodes a version 1 NTT request payload for a 20-byte EVM source manager address.
+pub fn make_ntt_v1_request_evm(
+    source_chain: u16,
+    source_manager: [u8; 20],
+    message_id: [u8; 32],
+) -> Vec<u8> {
+    make_ntt_v1_request(
+        source_chain,
+        left_pad_evm_address(source_manager),
+        message_id,
+    )
+}
+
+/// Encodes a version 2 NTT request payload.
+pub fn make_ntt_v2_request(
+    source_chain: u16,
+    source_manager: [u8; 32],
+    message_id: [u8; 32],
+    recipient_manager: [u8; 32],
+) -> Vec<u8> {
+    let mut out = Vec::with_capacity({
+        4 // type
+        + 2 // source chain
+        + 32 // source_manager
+        + 32 // message_id
+        + 32 // recipient_manager
+    });
+    out.extend_from_slice(REQ_NTT_V2);
+    out.extend_from_slice(&source_chain.to_be_bytes());
+    out.extend_from_slice(&source_manager);
+    out.extend_from_slice(&message_id);
+    out.extend_from_slice(&recipient_manager);
+    out
+}
+
 /// Encodes a version 1 CCTP request payload.
 pub fn make_cctp_v1_request(source_domain: u32, nonce: u64) -> Vec<u8> {
     let mut out = Vec::with_capacity({
@@ -51,8 +100,8 @@ pub fn make_cctp_v1_request(source_domain: u32, nonce: u64) -> Vec<u8> {
 }
 
 /// Encodes a version 2 CCTP request payload.
-/// This request currently assumes the Executor will auto detect the event off chain.
-/// That may change in the future, in which case this interface would change.
+/// This request assumes the Executor will auto detect the event off chain.
+/// See `make_cctp_v2_request_manual` for relayers which do not support auto discovery.
 pub fn make_cctp_v2_request() -> Vec<u8> {
     let mut out = Vec::with_capacity({
         4 // type
@@ -63,6 +112,196 @@ pub fn make_cctp_v2_request() -> Vec<u8> {
     out
 }
 
+/// Encodes a version 2 CCTP request payload which explicitly identifies the message rather than relying on auto discovery.
+pub fn make_cctp_v2_request_manual(source_domain: u32, nonce: [u8; 32]) -> Vec<u8> {
+    let mut out = Vec::with_capacity({
+        4 // type
+        + 1 // discovery
+        + 4 // source domain
+        + 32 // nonce
+    });
+    out.extend_from_slice(REQ_CCTP_V2);
+    out.extend_from_slice(&[0]); // manual
+    out.extend_from_slice(&source_domain.to_be_bytes());
+    out.extend_from_slice(&nonce);
+    out
+}
+
+/// Encodes a version 1 hash request payload, for protocols without a request type of their own.
+/// The `prefix` identifies the payload format and the relayer resolves the `content_hash` to the message off chain.
+pub fn make_hash_request(prefix: [u8; 4], content_hash: [u8; 32]) -> Vec<u8> {
+    let mut out = Vec::with_capacity({
+        4 // type
+        + 4 // prefix
+        + 32 // content hash
+    });
+    out.extend_from_slice(REQ_HASH_V1);
+    out.extend_from_slice(&prefix);
+    out.extend_from_slice(&content_hash);
+    out
+}
+
+/// Encodes a request payload with an arbitrary prefix, for request types not otherwise supported by this crate.
+/// Using the prefix of a known type produces bytes which will be parsed as that type rather than as `Request::Raw`.
+pub fn make_raw_request(prefix: [u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4 + body.len());
+    out.extend_from_slice(&prefix);
+    out.extend_from_slice(body);
+    out
+}
+
+/// Converts a 20-byte EVM address to a 32-byte universal address by left-padding it with zeros.
+fn left_pad_evm_address(evm_addr: [u8; 20]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[12..].copy_from_slice(&evm_addr);
+    out
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Request {
+    VaaV1 {
+        chain: u16,
+        address: [u8; 32],
+        sequence: u64,
+    },
+    NttV1 {
+        source_chain: u16,
+        source_manager: [u8; 32],
+        message_id: [u8; 32],
+    },
+    NttV2 {
+        source_chain: u16,
+        source_manager: [u8; 32],
+        message_id: [u8; 32],
+        recipient_manager: [u8; 32],
+    },
+    CctpV1 {
+        source_domain: u32,
+        nonce: u64,
+    },
+    /// A CCTP v2 request with the auto discovery flag set.
+    CctpV2,
+    /// A CCTP v2 request with the auto discovery flag unset, which identifies the message explicitly.
+    CctpV2Manual {
+        source_domain: u32,
+        nonce: [u8; 32],
+    },
+    HashV1 {
+        prefix: [u8; 4],
+        content_hash: [u8; 32],
+    },
+    /// A request with a prefix that does not match any of the known types.
+    Raw {
+        prefix: [u8; 4],
+        body: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestParseError {
+    /// The bytes are shorter or longer than the request type requires.
+    InvalidLength,
+    /// A field holds a value that is not currently supported.
+    InvalidValue,
+}
+
+impl Request {
+    /// Parses a request payload produced by one of the `make_*_request` functions.
+    /// Payloads with an unknown prefix are returned as `Request::Raw`.
+    pub fn parse(bytes: &[u8]) -> Result<Self, RequestParseError> {
+        let mut reader = Reader::new(bytes, RequestParseError::InvalidLength);
+        let prefix: [u8; 4] = reader.take()?;
+        let request = match &prefix {
+            REQ_VAA_V1 => Request::VaaV1 {
+                chain: u16::from_be_bytes(reader.take()?),
+                address: reader.take()?,
+                sequence: u64::from_be_bytes(reader.take()?),
+            },
+            REQ_NTT_V1 => Request::NttV1 {
+                source_chain: u16::from_be_bytes(reader.take()?),
+                source_manager: reader.take()?,
+                message_id: reader.take()?,
+            },
+            REQ_NTT_V2 => Request::NttV2 {
+                source_chain: u16::from_be_bytes(reader.take()?),
+                source_manager: reader.take()?,
+                message_id: reader.take()?,
+                recipient_manager: reader.take()?,
+            },
+            REQ_CCTP_V1 => Request::CctpV1 {
+                source_domain: u32::from_be_bytes(reader.take()?),
+                nonce: u64::from_be_bytes(reader.take()?),
+            },
+            REQ_CCTP_V2 => {
+                let [discovery] = reader.take()?;
+                match discovery {
+                    1 => Request::CctpV2,
+                    0 => Request::CctpV2Manual {
+                        source_domain: u32::from_be_bytes(reader.take()?),
+                        nonce: reader.take()?,
+                    },
+                    _ => return Err(RequestParseError::InvalidValue),
+                }
+            }
+            REQ_HASH_V1 => Request::HashV1 {
+                prefix: reader.take()?,
+                content_hash: reader.take()?,
+            },
+            _ => {
+                return Ok(Request::Raw {
+                    prefix,
+                    body: reader.remaining().to_vec(),
+                })
+            }
+        };
+        if !reader.remaining().is_empty() {
+            return Err(RequestParseError::InvalidLength);
+        }
+        Ok(request)
+    }
+
+    /// Encodes the request payload.
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Request::VaaV1 {
+                chain,
+                address,
+                sequence,
+            } => make_vaa_v1_request(*chain, *address, *sequence),
+            Request::NttV1 {
+                source_chain,
+                source_manager,
+                message_id,
+            } => make_ntt_v1_request(*source_chain, *source_manager, *message_id),
+            Request::NttV2 {
+                source_chain,
+                source_manager,
+                message_id,
+                recipient_manager,
+            } => make_ntt_v2_request(
+                *source_chain,
+                *source_manager,
+                *message_id,
+                *recipient_manager,
+            ),
+            Request::CctpV1 {
+                source_domain,
+                nonce,
+            } => make_cctp_v1_request(*source_domain, *nonce),
+            Request::CctpV2 => make_cctp_v2_request(),
+            Request::CctpV2Manual {
+                source_domain,
+                nonce,
+            } => make_cctp_v2_request_manual(*source_domain, *nonce),
+            Request::HashV1 {
+                prefix,
+                content_hash,
+            } => make_hash_request(*prefix, *content_hash),
+            Request::Raw { prefix, body } => make_raw_request(*prefix, body),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,6 +328,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_vaa_v1_evm() {
+        let result = make_vaa_v1_request_evm(
+            10002,
+            [
+                0xd4, 0xa6, 0xa7, 0x2a, 0x02, 0x55, 0x99, 0xfd, 0x73, 0x57, 0xc0, 0xf1, 0x57, 0xc7,
+                0x18, 0xd0, 0xf5, 0xe3, 0x8c, 0x76,
+            ],
+            29,
+        );
+        assert_eq!(
+            result,
+            [
+                0x45, 0x52, 0x56, 0x31, 0x27, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0xd4, 0xa6, 0xa7, 0x2a, 0x02, 0x55, 0x99, 0xfd, 0x73, 0x57,
+                0xc0, 0xf1, 0x57, 0xc7, 0x18, 0xd0, 0xf5, 0xe3, 0x8c, 0x76, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x1d
+            ]
+        );
+    }
+
     #[test]
     fn test_ntt_v1() {
         let mut sequence: [u8; 32] = [0; 32];
@@ -114,6 +374,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ntt_v1_evm() {
+        let mut sequence: [u8; 32] = [0; 32];
+        sequence[24..].copy_from_slice(&29_u64.to_be_bytes());
+        let source_manager = [
+            0xd4, 0xa6, 0xa7, 0x2a, 0x02, 0x55, 0x99, 0xfd, 0x73, 0x57, 0xc0, 0xf1, 0x57, 0xc7,
+            0x18, 0xd0, 0xf5, 0xe3, 0x8c, 0x76,
+        ];
+        let mut padded_source_manager = [0u8; 32];
+        padded_source_manager[12..].copy_from_slice(&source_manager);
+        assert_eq!(
+            make_ntt_v1_request_evm(10002, source_manager, sequence),
+            make_ntt_v1_request(10002, padded_source_manager, sequence)
+        );
+    }
+
     #[test]
     fn test_cctp_v1() {
         let result = make_cctp_v1_request(6, 6344);
@@ -131,4 +407,111 @@ mod tests {
         let result = make_cctp_v2_request();
         assert_eq!(result, [0x45, 0x52, 0x43, 0x32, 0x01]);
     }
+
+    #[test]
+    fn test_ntt_v2() {
+        let result = make_ntt_v2_request(10002, [0x11; 32], [0x22; 32], [0x33; 32]);
+        let mut expected = vec![0x45, 0x52, 0x4E, 0x32, 0x27, 0x12];
+        expected.extend_from_slice(&[0x11; 32]);
+        expected.extend_from_slice(&[0x22; 32]);
+        expected.extend_from_slice(&[0x33; 32]);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_cctp_v2_manual() {
+        let result = make_cctp_v2_request_manual(6, [0x44; 32]);
+        let mut expected = vec![0x45, 0x52, 0x43, 0x32, 0x00, 0x00, 0x00, 0x00, 0x06];
+        expected.extend_from_slice(&[0x44; 32]);
+        assert_eq!(result, expected);
+        assert_eq!(
+            Request::parse(&[0x45, 0x52, 0x43, 0x32, 0x00]),
+            Err(RequestParseError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_hash_v1() {
+        let result = make_hash_request(*b"APP1", [0x55; 32]);
+        let mut expected = vec![0x45, 0x52, 0x48, 0x31, 0x41, 0x50, 0x50, 0x31];
+        expected.extend_from_slice(&[0x55; 32]);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_raw() {
+        let result = make_raw_request(*b"ERX1", &[0x01, 0x02]);
+        assert_eq!(result, [0x45, 0x52, 0x58, 0x31, 0x01, 0x02]);
+        assert_eq!(
+            Request::parse(b"ERX1"),
+            Ok(Request::Raw {
+                prefix: *b"ERX1",
+                body: vec![]
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_roundtrip() {
+        let requests = [
+            Request::VaaV1 {
+                chain: 10002,
+                address: [0x11; 32],
+                sequence: 29,
+            },
+            Request::NttV1 {
+                source_chain: 10002,
+                source_manager: [0x11; 32],
+                message_id: [0x22; 32],
+            },
+            Request::NttV2 {
+                source_chain: 10002,
+                source_manager: [0x11; 32],
+                message_id: [0x22; 32],
+                recipient_manager: [0x33; 32],
+            },
+            Request::CctpV1 {
+                source_domain: 6,
+                nonce: 6344,
+            },
+            Request::CctpV2,
+            Request::CctpV2Manual {
+                source_domain: 6,
+                nonce: [0x44; 32],
+            },
+            Request::HashV1 {
+                prefix: *b"APP1",
+                content_hash: [0x55; 32],
+            },
+            Request::Raw {
+                prefix: *b"ERX1",
+                body: vec![0x01, 0x02, 0x03],
+            },
+        ];
+        for request in requests {
+            assert_eq!(Request::parse(&request.encode()), Ok(request));
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(
+            Request::parse(b"ERV"),
+            Err(RequestParseError::InvalidLength)
+        );
+        let mut request = make_cctp_v1_request(6, 6344);
+        request.push(0);
+        assert_eq!(
+            Request::parse(&request),
+            Err(RequestParseError::InvalidLength)
+        );
+        assert_eq!(
+            Request::parse(&request[..request.len() - 2]),
+            Err(RequestParseError::InvalidLength)
+        );
+        assert_eq!(
+            Request::parse(&[0x45, 0x52, 0x43, 0x32, 0x02]),
+            Err(RequestParseError::InvalidValue)
+        );
+    }
 }
@@ -0,0 +1,59 @@
+/// Reads `N` bytes at `offset`, failing with `err` rather than panicking if they are out of bounds.
+pub(crate) fn read_at<const N: usize, E>(
+    bytes: &[u8],
+    offset: usize,
+    err: E,
+) -> Result<[u8; N], E> {
+    let Some(slice) = offset.checked_add(N).and_then(|end| bytes.get(offset..end)) else {
+        return Err(err);
+    };
+    let mut out = [0u8; N];
+    out.copy_from_slice(slice);
+    Ok(out)
+}
+
+/// Reads consecutive fields from the front of the bytes, failing with `err` once they run out.
+pub(crate) struct Reader<'a, E> {
+    bytes: &'a [u8],
+    err: E,
+}
+
+impl<'a, E: Copy> Reader<'a, E> {
+    pub(crate) fn new(bytes: &'a [u8], err: E) -> Self {
+        Self { bytes, err }
+    }
+
+    pub(crate) fn take<const N: usize>(&mut self) -> Result<[u8; N], E> {
+        let out = read_at(self.bytes, 0, self.err)?;
+        self.bytes = &self.bytes[N..];
+        Ok(out)
+    }
+
+    /// Returns the bytes which have not been read yet.
+    pub(crate) fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_at() {
+        let bytes = [0x01, 0x02, 0x03];
+        assert_eq!(read_at(&bytes, 1, ()), Ok([0x02, 0x03]));
+        assert_eq!(read_at::<2, _>(&bytes, 2, ()), Err(()));
+        assert_eq!(read_at::<2, _>(&bytes, usize::MAX, ()), Err(()));
+    }
+
+    #[test]
+    fn test_reader() {
+        let mut reader = Reader::new(&[0x01, 0x02, 0x03], ());
+        assert_eq!(reader.take(), Ok([0x01]));
+        assert_eq!(reader.take::<3>(), Err(()));
+        assert_eq!(reader.remaining(), [0x02, 0x03]);
+        assert_eq!(reader.take(), Ok([0x02, 0x03]));
+        assert!(reader.remaining().is_empty());
+    }
+}
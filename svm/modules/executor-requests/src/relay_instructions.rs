@@ -0,0 +1,266 @@
+use crate::reader::Reader;
+
+const RECV_INST_TYPE_GAS: u8 = 1;
+const RECV_INST_TYPE_DROP_OFF: u8 = 2;
+
+const GAS_INSTRUCTION_LEN: usize = {
+    16 // gas limit
+    + 16 // msg value
+};
+const DROP_OFF_INSTRUCTION_LEN: usize = {
+    16 // drop off
+    + 32 // recipient
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayParseError {
+    /// The instruction type byte is not a known relay instruction.
+    UnsupportedType(u8),
+    /// The bytes ended before the instruction was complete.
+    Truncated,
+    /// More than one gas drop-off instruction was specified.
+    MultipleDropOff,
+    /// The summed values do not fit in a u128.
+    Overflow,
+    /// A msg value was specified without any gas limit, see `parse_relay_instructions_strict`.
+    GasZeroWithValue,
+}
+
+/// Encodes the gas parameters for the relayer.
+/// This instruction may be specified more than once. If so, the relayer should sum the values.
+pub fn make_gas_instruction(gas_limit: u128, msg_value: u128) -> Vec<u8> {
+    let mut out = Vec::with_capacity(1 + GAS_INSTRUCTION_LEN);
+    out.push(RECV_INST_TYPE_GAS);
+    out.extend_from_slice(&gas_limit.to_be_bytes());
+    out.extend_from_slice(&msg_value.to_be_bytes());
+    out
+}
+
+/// Encodes the gas drop off parameters for the relayer.
+pub fn make_gas_drop_off_instruction(drop_off: u128, recipient: [u8; 32]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(1 + DROP_OFF_INSTRUCTION_LEN);
+    out.push(RECV_INST_TYPE_DROP_OFF);
+    out.extend_from_slice(&drop_off.to_be_bytes());
+    out.extend_from_slice(&recipient);
+    out
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParsedRelayInstructions {
+    /// The sum of all gas instruction gas limits.
+    pub gas_limit: u128,
+    /// The sum of all gas instruction msg values, excluding any drop-off.
+    pub msg_value: u128,
+    /// The drop-off amount, or zero if none was specified.
+    pub drop_off: u128,
+    /// The recipient of the drop-off, if one was specified.
+    pub drop_off_recipient: Option<[u8; 32]>,
+}
+
+/// Parses relay instructions, returning the total `(gas_limit, msg_value)`.
+/// Gas instructions are summed and the drop-off amount, of which there may be at most one, is added to the msg value.
+pub fn parse_relay_instructions(bytes: &[u8]) -> Result<(u128, u128), RelayParseError> {
+    let parsed = parse_relay_instructions_full(bytes)?;
+    let msg_value = parsed
+        .msg_value
+        .checked_add(parsed.drop_off)
+        .ok_or(RelayParseError::Overflow)?;
+    Ok((parsed.gas_limit, msg_value))
+}
+
+/// Parses relay instructions like `parse_relay_instructions`, additionally rejecting a nonzero msg value with a zero gas limit.
+/// Such instructions are usually a mistake, since the value cannot be delivered without gas to execute with.
+pub fn parse_relay_instructions_strict(bytes: &[u8]) -> Result<(u128, u128), RelayParseError> {
+    let (gas_limit, msg_value) = parse_relay_instructions(bytes)?;
+    if gas_limit == 0 && msg_value != 0 {
+        return Err(RelayParseError::GasZeroWithValue);
+    }
+    Ok((gas_limit, msg_value))
+}
+
+/// Parses relay instructions, keeping the gas msg value and the drop-off separate and returning the drop-off recipient.
+pub fn parse_relay_instructions_full(
+    bytes: &[u8],
+) -> Result<ParsedRelayInstructions, RelayParseError> {
+    let mut parsed = ParsedRelayInstructions::default();
+    let mut reader = Reader::new(bytes, RelayParseError::Truncated);
+    while !reader.remaining().is_empty() {
+        let [ix_type] = reader.take()?;
+        match ix_type {
+            RECV_INST_TYPE_GAS => {
+                parsed.gas_limit = parsed
+                    .gas_limit
+                    .checked_add(u128::from_be_bytes(reader.take()?))
+                    .ok_or(RelayParseError::Overflow)?;
+                parsed.msg_value = parsed
+                    .msg_value
+                    .checked_add(u128::from_be_bytes(reader.take()?))
+                    .ok_or(RelayParseError::Overflow)?;
+            }
+            RECV_INST_TYPE_DROP_OFF => {
+                if parsed.drop_off_recipient.is_some() {
+                    return Err(RelayParseError::MultipleDropOff);
+                }
+                parsed.drop_off = u128::from_be_bytes(reader.take()?);
+                parsed.drop_off_recipient = Some(reader.take()?);
+            }
+            _ => return Err(RelayParseError::UnsupportedType(ix_type)),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Concatenates two sets of relay instructions, failing if either is invalid or the combined set is.
+/// Unlike extending the bytes directly, this catches a drop-off in both sets or gas totals which overflow.
+pub fn merge_relay_instructions(a: &[u8], b: &[u8]) -> Result<Vec<u8>, RelayParseError> {
+    // each set is parsed separately so a truncated instruction in `a` cannot be completed by bytes from `b`
+    parse_relay_instructions_full(a)?;
+    parse_relay_instructions_full(b)?;
+    let mut out = Vec::with_capacity(a.len() + b.len());
+    out.extend_from_slice(a);
+    out.extend_from_slice(b);
+    parse_relay_instructions_full(&out)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_instruction() {
+        let result = make_gas_instruction(1_000_000, 0);
+        assert_eq!(
+            result,
+            [
+                0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x0f, 0x42, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert_eq!(parse_relay_instructions(&[]), Ok((0, 0)));
+    }
+
+    #[test]
+    fn test_parse_sums_gas_and_drop_off() {
+        let mut bytes = make_gas_instruction(1_000_000, 5);
+        bytes.extend(make_gas_drop_off_instruction(7, [1; 32]));
+        bytes.extend(make_gas_instruction(250_000, 0));
+        assert_eq!(parse_relay_instructions(&bytes), Ok((1_250_000, 12)));
+    }
+
+    #[test]
+    fn test_parse_full() {
+        let mut bytes = make_gas_instruction(1_000_000, 5);
+        assert_eq!(
+            parse_relay_instructions_full(&bytes),
+            Ok(ParsedRelayInstructions {
+                gas_limit: 1_000_000,
+                msg_value: 5,
+                drop_off: 0,
+                drop_off_recipient: None,
+            })
+        );
+        bytes.extend(make_gas_drop_off_instruction(7, [1; 32]));
+        assert_eq!(
+            parse_relay_instructions_full(&bytes),
+            Ok(ParsedRelayInstructions {
+                gas_limit: 1_000_000,
+                msg_value: 5,
+                drop_off: 7,
+                drop_off_recipient: Some([1; 32]),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_drop_off_overflow() {
+        let mut bytes = make_gas_instruction(0, u128::MAX);
+        bytes.extend(make_gas_drop_off_instruction(1, [1; 32]));
+        assert!(parse_relay_instructions_full(&bytes).is_ok());
+        assert_eq!(
+            parse_relay_instructions(&bytes),
+            Err(RelayParseError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_parse_multiple_drop_off() {
+        let mut bytes = make_gas_drop_off_instruction(1, [1; 32]);
+        bytes.extend(make_gas_drop_off_instruction(2, [2; 32]));
+        assert_eq!(
+            parse_relay_instructions(&bytes),
+            Err(RelayParseError::MultipleDropOff)
+        );
+    }
+
+    #[test]
+    fn test_parse_truncated() {
+        let bytes = make_gas_instruction(1, 1);
+        assert_eq!(
+            parse_relay_instructions(&bytes[..bytes.len() - 1]),
+            Err(RelayParseError::Truncated)
+        );
+    }
+
+    #[test]
+    fn test_parse_unsupported_type() {
+        assert_eq!(
+            parse_relay_instructions(&[0xff]),
+            Err(RelayParseError::UnsupportedType(0xff))
+        );
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let bytes = make_gas_instruction(250_000, 5);
+        assert_eq!(parse_relay_instructions_strict(&bytes), Ok((250_000, 5)));
+        assert_eq!(parse_relay_instructions_strict(&[]), Ok((0, 0)));
+        let bytes = make_gas_instruction(0, 5);
+        assert_eq!(parse_relay_instructions(&bytes), Ok((0, 5)));
+        assert_eq!(
+            parse_relay_instructions_strict(&bytes),
+            Err(RelayParseError::GasZeroWithValue)
+        );
+        let bytes = make_gas_drop_off_instruction(7, [1; 32]);
+        assert_eq!(
+            parse_relay_instructions_strict(&bytes),
+            Err(RelayParseError::GasZeroWithValue)
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let a = make_gas_instruction(1_000_000, 5);
+        let b = make_gas_drop_off_instruction(7, [1; 32]);
+        let merged = merge_relay_instructions(&a, &b).unwrap();
+        assert_eq!(merged, [a.clone(), b.clone()].concat());
+        assert_eq!(parse_relay_instructions(&merged), Ok((1_000_000, 12)));
+        assert_eq!(
+            merge_relay_instructions(&b, &b),
+            Err(RelayParseError::MultipleDropOff)
+        );
+        assert_eq!(
+            merge_relay_instructions(&make_gas_instruction(u128::MAX, 0), &a),
+            Err(RelayParseError::Overflow)
+        );
+        assert_eq!(
+            merge_relay_instructions(&a[..1], &a[1..]),
+            Err(RelayParseError::Truncated)
+        );
+    }
+
+    #[test]
+    fn test_parse_overflow() {
+        let mut bytes = make_gas_instruction(u128::MAX, 0);
+        bytes.extend(make_gas_instruction(1, 0));
+        assert_eq!(
+            parse_relay_instructions(&bytes),
+            Err(RelayParseError::Overflow)
+        );
+    }
+}
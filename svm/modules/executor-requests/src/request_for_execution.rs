@@ -0,0 +1,150 @@
+use crate::{
+    relay_instructions::{merge_relay_instructions, RelayParseError},
+    Request,
+};
+
+/// The current `RequestForExecutionArgs` layout version.
+/// Version 0 is the layout used before the version byte was added, now prefixed with it.
+pub const REQUEST_FOR_EXECUTION_ARGS_VERSION: u8 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionRequestBuildError {
+    /// A variable length field is longer than its u32 length prefix can encode.
+    TooLong,
+}
+
+/// Builds the serialized `RequestForExecutionArgs` for the executor's `request_for_execution` instruction.
+/// The output matches the Borsh layout of the args, so fields are always written in the order the program expects.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionRequestBuilder {
+    amount: u64,
+    dst_chain: u16,
+    dst_addr: [u8; 32],
+    refund_addr: [u8; 32],
+    signed_quote_bytes: Vec<u8>,
+    request_bytes: Vec<u8>,
+    relay_instructions: Vec<u8>,
+}
+
+impl ExecutionRequestBuilder {
+    pub fn new(dst_chain: u16, dst_addr: [u8; 32]) -> Self {
+        Self {
+            dst_chain,
+            dst_addr,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the amount to pay the payee for execution.
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    /// Sets the native address to refund excess payment to.
+    pub fn refund_addr(mut self, refund_addr: [u8; 32]) -> Self {
+        self.refund_addr = refund_addr;
+        self
+    }
+
+    /// Sets the signed quote, e.g. the output of `SignedQuoteBuilder::build`.
+    pub fn signed_quote_bytes(mut self, signed_quote_bytes: Vec<u8>) -> Self {
+        self.signed_quote_bytes = signed_quote_bytes;
+        self
+    }
+
+    /// Sets the request to execute. Use `Request::Raw` for types not otherwise supported by this crate.
+    pub fn request(mut self, request: &Request) -> Self {
+        self.request_bytes = request.encode();
+        self
+    }
+
+    /// Appends encoded relay instructions, e.g. the output of `make_gas_instruction`.
+    /// Fails if the instructions are invalid or conflict with those already added, e.g. a second drop-off.
+    pub fn relay_instruction(mut self, relay_instruction: &[u8]) -> Result<Self, RelayParseError> {
+        self.relay_instructions =
+            merge_relay_instructions(&self.relay_instructions, relay_instruction)?;
+        Ok(self)
+    }
+
+    pub fn build(&self) -> Result<Vec<u8>, ExecutionRequestBuildError> {
+        let mut out = Vec::with_capacity({
+            1 // version
+            + 8 // amount
+            + 2 // dst chain
+            + 32 // dst addr
+            + 32 // refund addr
+            + 4 + self.signed_quote_bytes.len()
+            + 4 + self.request_bytes.len()
+            + 4 + self.relay_instructions.len()
+        });
+        out.push(REQUEST_FOR_EXECUTION_ARGS_VERSION);
+        out.extend_from_slice(&self.amount.to_le_bytes());
+        out.extend_from_slice(&self.dst_chain.to_le_bytes());
+        out.extend_from_slice(&self.dst_addr);
+        out.extend_from_slice(&self.refund_addr);
+        write_bytes(&mut out, &self.signed_quote_bytes)?;
+        write_bytes(&mut out, &self.request_bytes)?;
+        write_bytes(&mut out, &self.relay_instructions)?;
+        Ok(out)
+    }
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) -> Result<(), ExecutionRequestBuildError> {
+    let len = u32::try_from(bytes.len()).map_err(|_| ExecutionRequestBuildError::TooLong)?;
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(bytes);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::relay_instructions::{make_gas_drop_off_instruction, make_gas_instruction};
+
+    #[test]
+    fn test_build() {
+        let result = ExecutionRequestBuilder::new(2, [0xaa; 32])
+            .amount(1000)
+            .refund_addr([0xbb; 32])
+            .signed_quote_bytes(vec![0x01, 0x02])
+            .request(&Request::Raw {
+                prefix: *b"ERX1",
+                body: vec![0x03],
+            })
+            .build()
+            .unwrap();
+        let mut expected = vec![
+            0x00, 0xe8, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00,
+        ];
+        expected.extend_from_slice(&[0xaa; 32]);
+        expected.extend_from_slice(&[0xbb; 32]);
+        expected.extend_from_slice(&[0x02, 0x00, 0x00, 0x00, 0x01, 0x02]);
+        expected.extend_from_slice(&[0x05, 0x00, 0x00, 0x00, 0x45, 0x52, 0x58, 0x31, 0x03]);
+        expected.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_relay_instruction() {
+        let gas = make_gas_instruction(250_000, 0);
+        let drop_off = make_gas_drop_off_instruction(7, [1; 32]);
+        let builder = ExecutionRequestBuilder::new(2, [0xaa; 32])
+            .relay_instruction(&gas)
+            .unwrap()
+            .relay_instruction(&drop_off)
+            .unwrap();
+        assert_eq!(
+            builder.relay_instructions,
+            [gas.clone(), drop_off.clone()].concat()
+        );
+        assert_eq!(
+            builder.relay_instruction(&drop_off),
+            Err(RelayParseError::MultipleDropOff)
+        );
+        assert_eq!(
+            ExecutionRequestBuilder::default().relay_instruction(&gas[..1]),
+            Err(RelayParseError::Truncated)
+        );
+    }
+}
@@ -0,0 +1,359 @@
+use crate::reader::read_at;
+
+/// Length of the signed quote header, which is shared by all quote versions.
+pub const SIGNED_QUOTE_HEADER_LEN: usize = {
+    4 // prefix
+    + 20 // quoter address
+    + 32 // payee address
+    + 2 // source chain
+    + 2 // destination chain
+    + 8 // expiry time
+};
+
+#[cfg(feature = "signing")]
+pub use libsecp256k1::SecretKey;
+
+const QUOTE_V1_PREFIX: &[u8; 4] = b"EQ01";
+
+/// Length of the quoter's signature which follows the quote body.
+pub const SIGNATURE_LEN: usize = 65;
+
+/// Length of a complete version 1 (`EQ01`) signed quote.
+pub const SIGNED_QUOTE_V1_LEN: usize = {
+    SIGNED_QUOTE_HEADER_LEN
+    + 8 // base fee
+    + 8 // destination gas price
+    + 8 // source price
+    + 8 // destination price
+    + SIGNATURE_LEN
+};
+
+/// Returns the complete length of a signed quote with the given prefix, or `None` if the version is unknown.
+pub fn signed_quote_len(prefix: &[u8; 4]) -> Option<usize> {
+    match prefix {
+        QUOTE_V1_PREFIX => Some(SIGNED_QUOTE_V1_LEN),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignedQuoteHeader {
+    pub prefix: [u8; 4],
+    pub quoter_address: [u8; 20],
+    pub payee: [u8; 32],
+    pub src_chain: u16,
+    pub dst_chain: u16,
+    pub expiry_time: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignedQuoteParseError {
+    /// The bytes are shorter than the signed quote header.
+    InvalidLength,
+}
+
+// Offsets of the header fields within a signed quote
+const PREFIX_OFFSET: usize = 0;
+const QUOTER_ADDRESS_OFFSET: usize = 4;
+const PAYEE_OFFSET: usize = 24;
+const SRC_CHAIN_OFFSET: usize = 56;
+const DST_CHAIN_OFFSET: usize = 58;
+const EXPIRY_TIME_OFFSET: usize = 60;
+
+/// Parses the header of a signed quote, ignoring any bytes after it.
+pub fn parse_signed_quote_header(bytes: &[u8]) -> Result<SignedQuoteHeader, SignedQuoteParseError> {
+    let err = SignedQuoteParseError::InvalidLength;
+    Ok(SignedQuoteHeader {
+        prefix: read_at(bytes, PREFIX_OFFSET, err)?,
+        quoter_address: read_at(bytes, QUOTER_ADDRESS_OFFSET, err)?,
+        payee: read_at(bytes, PAYEE_OFFSET, err)?,
+        src_chain: u16::from_be_bytes(read_at(bytes, SRC_CHAIN_OFFSET, err)?),
+        dst_chain: u16::from_be_bytes(read_at(bytes, DST_CHAIN_OFFSET, err)?),
+        expiry_time: u64::from_be_bytes(read_at(bytes, EXPIRY_TIME_OFFSET, err)?),
+    })
+}
+
+/// The body of a version 1 (`EQ01`) quote, which follows the header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuoteBodyV1 {
+    /// The base fee, in source chain native currency.
+    pub base_fee: u64,
+    /// The current gas price on the destination chain.
+    pub dst_gas_price: u64,
+    /// The USD price, in 10^10, of the source chain native currency.
+    pub src_price: u64,
+    /// The USD price, in 10^10, of the destination chain native currency.
+    pub dst_price: u64,
+}
+
+/// Encodes the message a quoter signs for a version 1 (`EQ01`) quote, i.e. the header followed by the `body`.
+/// Off-chain signers and on-chain verification should both use this so they agree on the exact bytes.
+pub fn canonical_quote_message(
+    quoter_address: [u8; 20],
+    src_chain: u16,
+    dst_chain: u16,
+    expiry_time: u64,
+    payee: [u8; 32],
+    body: &QuoteBodyV1,
+) -> Vec<u8> {
+    let mut out = Vec::with_capacity(SIGNED_QUOTE_V1_LEN - SIGNATURE_LEN);
+    out.extend_from_slice(QUOTE_V1_PREFIX);
+    out.extend_from_slice(&quoter_address);
+    out.extend_from_slice(&payee);
+    out.extend_from_slice(&src_chain.to_be_bytes());
+    out.extend_from_slice(&dst_chain.to_be_bytes());
+    out.extend_from_slice(&expiry_time.to_be_bytes());
+    out.extend_from_slice(&body.base_fee.to_be_bytes());
+    out.extend_from_slice(&body.dst_gas_price.to_be_bytes());
+    out.extend_from_slice(&body.src_price.to_be_bytes());
+    out.extend_from_slice(&body.dst_price.to_be_bytes());
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignedQuoteBuildError {
+    /// No signature was provided for the quote.
+    MissingSignature,
+}
+
+/// Builds a version 1 (`EQ01`) signed quote, laying out the header at the offsets the executor reads.
+/// The quote must be signed by the quoter over `unsigned_bytes`, either off chain with the signature provided to the builder,
+/// or with `sign` when the `signing` feature is enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedQuoteBuilder {
+    quoter_address: [u8; 20],
+    payee: [u8; 32],
+    src_chain: u16,
+    dst_chain: u16,
+    expiry_time: u64,
+    body: QuoteBodyV1,
+    signature: Option<[u8; SIGNATURE_LEN]>,
+}
+
+impl SignedQuoteBuilder {
+    /// Creates a quote from `src_chain` to `dst_chain` paying `payee` and valid until `expiry_time`, with all prices zero.
+    pub fn new(src_chain: u16, dst_chain: u16, expiry_time: u64, payee: [u8; 32]) -> Self {
+        Self {
+            quoter_address: [0; 20],
+            payee,
+            src_chain,
+            dst_chain,
+            expiry_time,
+            body: QuoteBodyV1::default(),
+            signature: None,
+        }
+    }
+
+    /// Sets the EVM address of the quoter's signing key.
+    pub fn quoter_address(mut self, quoter_address: [u8; 20]) -> Self {
+        self.quoter_address = quoter_address;
+        self
+    }
+
+    /// Sets the base fee, in source chain native currency.
+    pub fn base_fee(mut self, base_fee: u64) -> Self {
+        self.body.base_fee = base_fee;
+        self
+    }
+
+    /// Sets the current gas price on the destination chain.
+    pub fn dst_gas_price(mut self, dst_gas_price: u64) -> Self {
+        self.body.dst_gas_price = dst_gas_price;
+        self
+    }
+
+    /// Sets the USD price, in 10^10, of the source chain native currency.
+    pub fn src_price(mut self, src_price: u64) -> Self {
+        self.body.src_price = src_price;
+        self
+    }
+
+    /// Sets the USD price, in 10^10, of the destination chain native currency.
+    pub fn dst_price(mut self, dst_price: u64) -> Self {
+        self.body.dst_price = dst_price;
+        self
+    }
+
+    /// Sets the quoter's signature of `unsigned_bytes`.
+    pub fn signature(mut self, signature: [u8; SIGNATURE_LEN]) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    /// Encodes the header and body, i.e. everything preceding the signature.
+    pub fn unsigned_bytes(&self) -> Vec<u8> {
+        canonical_quote_message(
+            self.quoter_address,
+            self.src_chain,
+            self.dst_chain,
+            self.expiry_time,
+            self.payee,
+            &self.body,
+        )
+    }
+
+    /// Signs the quote with the quoter's secp256k1 key, first setting the quoter address to the key's EVM address.
+    /// The signature is over the keccak256 hash of `unsigned_bytes`, with a recovery id of 27 or 28 as `ecrecover` expects.
+    #[cfg(feature = "signing")]
+    pub fn sign(self, secret_key: &libsecp256k1::SecretKey) -> Self {
+        let public_key = libsecp256k1::PublicKey::from_secret_key(secret_key);
+        let mut quoter_address = [0u8; 20];
+        quoter_address.copy_from_slice(&keccak256(&public_key.serialize()[1..])[12..]);
+        let builder = self.quoter_address(quoter_address);
+        let message = libsecp256k1::Message::parse(&keccak256(&builder.unsigned_bytes()));
+        let (signature, recovery_id) = libsecp256k1::sign(&message, secret_key);
+        let mut out = [0u8; SIGNATURE_LEN];
+        out[..64].copy_from_slice(&signature.serialize());
+        out[64] = 27 + recovery_id.serialize();
+        builder.signature(out)
+    }
+
+    /// Encodes the complete signed quote.
+    pub fn build(&self) -> Result<Vec<u8>, SignedQuoteBuildError> {
+        let signature = self
+            .signature
+            .ok_or(SignedQuoteBuildError::MissingSignature)?;
+        let mut out = self.unsigned_bytes();
+        out.extend_from_slice(&signature);
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "signing")]
+fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    use sha3::{Digest, Keccak256};
+    Keccak256::digest(bytes).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_len() {
+        assert_eq!(SIGNED_QUOTE_HEADER_LEN, 68);
+    }
+
+    #[test]
+    fn test_parse_signed_quote_header() {
+        let mut bytes = b"EQ01".to_vec();
+        bytes.extend_from_slice(&[0x11; 20]);
+        bytes.extend_from_slice(&[0x22; 32]);
+        bytes.extend_from_slice(&[0x00, 0x01, 0x00, 0x02]);
+        bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x00, 0x67, 0x8a, 0x6b, 0x80]);
+        assert_eq!(bytes.len(), SIGNED_QUOTE_HEADER_LEN);
+        let expected = SignedQuoteHeader {
+            prefix: *b"EQ01",
+            quoter_address: [0x11; 20],
+            payee: [0x22; 32],
+            src_chain: 1,
+            dst_chain: 2,
+            expiry_time: 1737124736,
+        };
+        assert_eq!(parse_signed_quote_header(&bytes), Ok(expected));
+        bytes.push(0xff);
+        assert_eq!(parse_signed_quote_header(&bytes), Ok(expected));
+        assert_eq!(
+            parse_signed_quote_header(&bytes[..SIGNED_QUOTE_HEADER_LEN - 1]),
+            Err(SignedQuoteParseError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_signed_quote_len() {
+        assert_eq!(SIGNED_QUOTE_V1_LEN, 165);
+        assert_eq!(signed_quote_len(b"EQ01"), Some(SIGNED_QUOTE_V1_LEN));
+        assert_eq!(signed_quote_len(b"EQ02"), None);
+    }
+
+    #[test]
+    fn test_canonical_quote_message() {
+        let body = QuoteBodyV1 {
+            base_fee: 10000,
+            dst_gas_price: 1000000007,
+            src_price: 32308000000000,
+            dst_price: 6759000000000,
+        };
+        let result = canonical_quote_message([0x11; 20], 1, 2, 1737124736, [0x22; 32], &body);
+        let mut expected = b"EQ01".to_vec();
+        expected.extend_from_slice(&[0x11; 20]);
+        expected.extend_from_slice(&[0x22; 32]);
+        expected.extend_from_slice(&[0x00, 0x01, 0x00, 0x02]);
+        expected.extend_from_slice(&[0x00, 0x00, 0x00, 0x00, 0x67, 0x8a, 0x6b, 0x80]);
+        expected.extend_from_slice(&[
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x27, 0x10, 0x00, 0x00, 0x00, 0x00, 0x3b, 0x9a,
+            0xca, 0x07, 0x00, 0x00, 0x1d, 0x62, 0x4a, 0xdd, 0x08, 0x00, 0x00, 0x00, 0x06, 0x25,
+            0xb3, 0xcb, 0x46, 0x00,
+        ]);
+        assert_eq!(result, expected);
+        assert_eq!(result.len(), SIGNED_QUOTE_V1_LEN - SIGNATURE_LEN);
+    }
+
+    #[test]
+    fn test_signed_quote_builder() {
+        let builder = SignedQuoteBuilder::new(1, 2, 1737124736, [0x22; 32])
+            .quoter_address([0x11; 20])
+            .base_fee(10000)
+            .dst_gas_price(1000000007)
+            .src_price(32308000000000)
+            .dst_price(6759000000000);
+        assert_eq!(
+            builder.build(),
+            Err(SignedQuoteBuildError::MissingSignature)
+        );
+        let result = builder.signature([0xcd; SIGNATURE_LEN]).build().unwrap();
+        assert_eq!(Some(result.len()), signed_quote_len(b"EQ01"));
+        assert_eq!(
+            parse_signed_quote_header(&result),
+            Ok(SignedQuoteHeader {
+                prefix: *b"EQ01",
+                quoter_address: [0x11; 20],
+                payee: [0x22; 32],
+                src_chain: 1,
+                dst_chain: 2,
+                expiry_time: 1737124736,
+            })
+        );
+        assert_eq!(
+            result[SIGNED_QUOTE_HEADER_LEN..SIGNED_QUOTE_HEADER_LEN + 32],
+            [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x27, 0x10, 0x00, 0x00, 0x00, 0x00, 0x3b, 0x9a,
+                0xca, 0x07, 0x00, 0x00, 0x1d, 0x62, 0x4a, 0xdd, 0x08, 0x00, 0x00, 0x00, 0x06, 0x25,
+                0xb3, 0xcb, 0x46, 0x00
+            ]
+        );
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_sign() {
+        let mut secret_key = [0u8; 32];
+        secret_key[31] = 1;
+        let secret_key = libsecp256k1::SecretKey::parse(&secret_key).unwrap();
+        let builder = SignedQuoteBuilder::new(1, 2, 1737124736, [0x22; 32])
+            .base_fee(10000)
+            .sign(&secret_key);
+        let result = builder.build().unwrap();
+        let header = parse_signed_quote_header(&result).unwrap();
+        let signature = &result[SIGNED_QUOTE_V1_LEN - SIGNATURE_LEN..];
+        let message = libsecp256k1::Message::parse(&keccak256(&builder.unsigned_bytes()));
+        let recovered = libsecp256k1::recover(
+            &message,
+            &libsecp256k1::Signature::parse_standard_slice(&signature[..64]).unwrap(),
+            &libsecp256k1::RecoveryId::parse_rpc(signature[64]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            header.quoter_address,
+            keccak256(&recovered.serialize()[1..])[12..]
+        );
+        // the well known address of private key 1
+        assert_eq!(
+            header.quoter_address,
+            [
+                0x7e, 0x5f, 0x45, 0x52, 0x09, 0x1a, 0x69, 0x12, 0x5d, 0x5d, 0xfc, 0xb7, 0xb8, 0xc2,
+                0x65, 0x90, 0x29, 0x39, 0x5b, 0xdf
+            ]
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use executor_requests::{
+    make_raw_request,
+    relay_instructions::{
+        make_gas_drop_off_instruction, make_gas_instruction, parse_relay_instructions,
+    },
+    signed_quote::{parse_signed_quote_header, SIGNED_QUOTE_HEADER_LEN},
+    Request,
+};
+use proptest::prelude::*;
+
+const KNOWN_PREFIXES: [&[u8; 4]; 6] = [b"ERV1", b"ERN1", b"ERN2", b"ERC1", b"ERC2", b"ERH1"];
+
+fn arb_request() -> impl Strategy<Value = Request> {
+    prop_oneof![
+        (any::<u16>(), any::<[u8; 32]>(), any::<u64>()).prop_map(|(chain, address, sequence)| {
+            Request::VaaV1 {
+                chain,
+                address,
+                sequence,
+            }
+        }),
+        (any::<u16>(), any::<[u8; 32]>(), any::<[u8; 32]>()).prop_map(
+            |(source_chain, source_manager, message_id)| Request::NttV1 {
+                source_chain,
+                source_manager,
+                message_id,
+            }
+        ),
+        (
+            any::<u16>(),
+            any::<[u8; 32]>(),
+            any::<[u8; 32]>(),
+            any::<[u8; 32]>()
+        )
+            .prop_map(
+                |(source_chain, source_manager, message_id, recipient_manager)| {
+                    Request::NttV2 {
+                        source_chain,
+                        source_manager,
+                        message_id,
+                        recipient_manager,
+                    }
+                }
+            ),
+        (any::<u32>(), any::<u64>()).prop_map(|(source_domain, nonce)| Request::CctpV1 {
+            source_domain,
+            nonce,
+        }),
+        Just(Request::CctpV2),
+        (any::<u32>(), any::<[u8; 32]>()).prop_map(|(source_domain, nonce)| {
+            Request::CctpV2Manual {
+                source_domain,
+                nonce,
+            }
+        }),
+        (any::<[u8; 4]>(), any::<[u8; 32]>()).prop_map(|(prefix, content_hash)| {
+            Request::HashV1 {
+                prefix,
+                content_hash,
+            }
+        }),
+        (
+            any::<[u8; 4]>()
+                .prop_filter("known prefix", |prefix| !KNOWN_PREFIXES.contains(&prefix)),
+            proptest::collection::vec(any::<u8>(), 0..64)
+        )
+            .prop_map(|(prefix, body)| Request::Raw { prefix, body }),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn request_roundtrip(request in arb_request()) {
+        prop_assert_eq!(Request::parse(&request.encode()), Ok(request));
+    }
+
+    #[test]
+    fn truncated_known_request_errors(request in arb_request(), cut in 1usize..128) {
+        let bytes = request.encode();
+        prop_assume!(!matches!(request, Request::Raw { .. }));
+        let len = bytes.len().saturating_sub(cut);
+        prop_assert!(Request::parse(&bytes[..len]).is_err());
+    }
+
+    #[test]
+    fn raw_request_roundtrip(prefix in any::<[u8; 4]>(), body in proptest::collection::vec(any::<u8>(), 0..64)) {
+        prop_assume!(!KNOWN_PREFIXES.contains(&&prefix));
+        prop_assert_eq!(
+            Request::parse(&make_raw_request(prefix, &body)),
+            Ok(Request::Raw { prefix, body })
+        );
+    }
+
+    #[test]
+    fn relay_instructions_roundtrip(
+        gas in proptest::collection::vec((0..=u64::MAX as u128, 0..=u64::MAX as u128), 0..8),
+        drop_off in proptest::option::of((0..=u64::MAX as u128, any::<[u8; 32]>())),
+    ) {
+        let mut bytes = Vec::new();
+        let mut expected = (0u128, 0u128);
+        for (gas_limit, msg_value) in &gas {
+            bytes.extend(make_gas_instruction(*gas_limit, *msg_value));
+            expected.0 += gas_limit;
+            expected.1 += msg_value;
+        }
+        if let Some((amount, recipient)) = drop_off {
+            bytes.extend(make_gas_drop_off_instruction(amount, recipient));
+            expected.1 += amount;
+        }
+        prop_assert_eq!(parse_relay_instructions(&bytes), Ok(expected));
+    }
+
+    #[test]
+    fn garbage_never_panics(bytes in proptest::collection::vec(any::<u8>(), 0..256)) {
+        let _ = Request::parse(&bytes);
+        let _ = parse_relay_instructions(&bytes);
+        let header = parse_signed_quote_header(&bytes);
+        prop_assert_eq!(header.is_ok(), bytes.len() >= SIGNED_QUOTE_HEADER_LEN);
+    }
+}
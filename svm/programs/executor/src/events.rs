@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// Discriminator prefixing the Borsh encoded `RequestForExecutionEvent` in the program logs.
+/// This is `sha256("event:RequestForExecutionEvent")[..8]`, as derived by Anchor.
+pub const REQUEST_FOR_EXECUTION_EVENT_DISCRIMINATOR: [u8; 8] =
+    [0x74, 0x85, 0xe1, 0x1d, 0xbf, 0x98, 0x18, 0x93];
+
+/// Emitted by `request_for_execution`, mirroring the EVM `RequestForExecution` event.
+/// The layout is the Borsh encoding of the fields in order, following the 8-byte discriminator.
+#[event]
+pub struct RequestForExecutionEvent {
+    pub quoter_address: [u8; 20],
+    pub amt_paid: u64,
+    /// The mint the payment was made in, or `None` for lamports.
+    pub mint: Option<Pubkey>,
+    pub dst_chain: u16,
+    pub dst_addr: [u8; 32],
+    pub refund_addr: Pubkey,
+    pub signed_quote: Vec<u8>,
+    pub request_bytes: Vec<u8>,
+    pub relay_instructions: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn test_discriminator() {
+        assert_eq!(
+            REQUEST_FOR_EXECUTION_EVENT_DISCRIMINATOR,
+            RequestForExecutionEvent::DISCRIMINATOR
+        );
+    }
+}
@@ -1,11 +1,31 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use executor_requests::{
+    relay_instructions::parse_relay_instructions,
+    request_for_execution::REQUEST_FOR_EXECUTION_ARGS_VERSION,
+    signed_quote::{parse_signed_quote_header, signed_quote_len},
+};
+
+pub mod events;
+
+use events::RequestForExecutionEvent;
 
 declare_id!("execXUrAsMnqMmTHj5m7N1YQgsDz3cwGLYCYyuDRciV");
 
 // TODO: cfg_if
 static OUR_CHAIN: u16 = 1;
 
-static SIGNED_QUOTE_HEADER_LEN: usize = 68;
+// Upper bounds on the variable length args, all comfortably above what fits in a transaction alongside the others
+static MAX_SIGNED_QUOTE_LEN: usize = 512;
+static MAX_REQUEST_BYTES_LEN: usize = 512;
+static MAX_RELAY_INSTRUCTIONS_LEN: usize = 512;
+
+/// Minimum seconds of validity a quote must have left to be accepted.
+static MIN_QUOTE_VALIDITY: u64 = 20;
+
+/// Upper bound on a lamport payment, far above any expected execution cost, to guard against client bugs draining the payer.
+/// Token payments are not capped since their units depend on the mint.
+static MAX_LAMPORTS_AMOUNT: u64 = 100 * anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
 
 #[program]
 pub mod executor {
@@ -16,76 +36,177 @@ pub mod executor {
         args: RequestForExecutionArgs,
     ) -> Result<()> {
         let RequestForExecutionArgs {
+            version,
             amount,
             dst_chain,
+            dst_addr,
+            refund_addr,
             signed_quote_bytes,
-            ..
+            request_bytes,
+            relay_instructions,
         } = args;
         require!(
-            signed_quote_bytes.len() >= SIGNED_QUOTE_HEADER_LEN,
+            version == REQUEST_FOR_EXECUTION_ARGS_VERSION,
             ExecutorErrors::InvalidArguments
         );
-        {
-            let quote_src_chain = u16::from_be_bytes(
-                signed_quote_bytes[56..58]
-                    .try_into()
-                    .map_err(|_| ExecutorErrors::InvalidArguments)?,
-            );
-            let quote_dst_chain = u16::from_be_bytes(
-                signed_quote_bytes[58..60]
-                    .try_into()
-                    .map_err(|_| ExecutorErrors::InvalidArguments)?,
-            );
-            let expiry_time = u64::from_be_bytes(
-                signed_quote_bytes[60..68]
-                    .try_into()
-                    .map_err(|_| ExecutorErrors::InvalidArguments)?,
-            );
-            require!(
-                quote_src_chain == OUR_CHAIN,
-                ExecutorErrors::QuoteSrcChainMismatch
-            );
+        require!(
+            signed_quote_bytes.len() <= MAX_SIGNED_QUOTE_LEN,
+            ExecutorErrors::InvalidArguments
+        );
+        require!(
+            request_bytes.len() <= MAX_REQUEST_BYTES_LEN,
+            ExecutorErrors::InvalidArguments
+        );
+        require!(
+            relay_instructions.len() <= MAX_RELAY_INSTRUCTIONS_LEN,
+            ExecutorErrors::InvalidRelayInstructions
+        );
+        let quote_header = parse_signed_quote_header(&signed_quote_bytes)
+            .map_err(|_| ExecutorErrors::InvalidArguments)?;
+        // quotes of a known version must not carry trailing bytes which an off-chain parser could misinterpret
+        if let Some(expected_len) = signed_quote_len(&quote_header.prefix) {
             require!(
-                quote_dst_chain == dst_chain,
-                ExecutorErrors::QuoteDstChainMismatch,
+                signed_quote_bytes.len() == expected_len,
+                ExecutorErrors::InvalidArguments
             );
+        }
+        validate_relay_instructions(&relay_instructions)?;
+        require!(
+            quote_header.src_chain == OUR_CHAIN,
+            ExecutorErrors::QuoteSrcChainMismatch
+        );
+        require!(
+            quote_header.dst_chain == dst_chain,
+            ExecutorErrors::QuoteDstChainMismatch,
+        );
+        {
+            let now: u64 = Clock::get()?
+                .unix_timestamp
+                .try_into()
+                .map_err(|_| ExecutorErrors::QuoteExpired)?;
+            require!(quote_header.expiry_time > now, ExecutorErrors::QuoteExpired);
             require!(
-                expiry_time
-                    > Clock::get()?
-                        .unix_timestamp
-                        .try_into()
-                        .map_err(|_| ExecutorErrors::QuoteExpired)?,
-                ExecutorErrors::QuoteExpired
+                quote_header.expiry_time >= now.saturating_add(MIN_QUOTE_VALIDITY),
+                ExecutorErrors::QuoteExpiresTooSoon
             );
         }
         require!(
-            ctx.accounts.payee.key.as_ref() == &signed_quote_bytes[24..56],
+            ct_eq(ctx.accounts.payee.key.as_ref(), &quote_header.payee),
             ExecutorErrors::QuotePayeeMismatch
         );
+        // a payment to oneself would succeed without anyone being paid for execution
+        require!(
+            !ct_eq(
+                ctx.accounts.payer.key.as_ref(),
+                ctx.accounts.payee.key.as_ref()
+            ),
+            ExecutorErrors::InvalidArguments
+        );
 
         let from_account = &ctx.accounts.payer;
         let to_account = &ctx.accounts.payee;
 
-        let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
-            from_account.key,
-            to_account.key,
-            amount,
-        );
+        let mint = match (
+            &ctx.accounts.mint,
+            &ctx.accounts.payer_token_account,
+            &ctx.accounts.payee_token_account,
+            &ctx.accounts.token_program,
+        ) {
+            (None, None, None, None) => {
+                require!(
+                    amount <= MAX_LAMPORTS_AMOUNT,
+                    ExecutorErrors::InvalidArguments
+                );
+                // the payer must be left rent exempt, otherwise the transfer fails with an opaque runtime error
+                let required = amount
+                    .checked_add(Rent::get()?.minimum_balance(0))
+                    .ok_or(ExecutorErrors::InsufficientFunds)?;
+                require!(
+                    from_account.lamports() >= required,
+                    ExecutorErrors::InsufficientFunds
+                );
+                let transfer_instruction =
+                    anchor_lang::solana_program::system_instruction::transfer(
+                        from_account.key,
+                        to_account.key,
+                        amount,
+                    );
 
-        anchor_lang::solana_program::program::invoke_signed(
-            &transfer_instruction,
-            &[
-                from_account.to_account_info(),
-                to_account.clone(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[],
-        )?;
+                anchor_lang::solana_program::program::invoke_signed(
+                    &transfer_instruction,
+                    &[
+                        from_account.to_account_info(),
+                        to_account.clone(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                    &[],
+                )?;
+                None
+            }
+            (
+                Some(mint),
+                Some(payer_token_account),
+                Some(payee_token_account),
+                Some(token_program),
+            ) => {
+                // the quote's payee is the owner of the token account receiving the payment
+                require!(
+                    ct_eq(payee_token_account.owner.as_ref(), to_account.key.as_ref()),
+                    ExecutorErrors::QuotePayeeMismatch
+                );
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        token_program.to_account_info(),
+                        TransferChecked {
+                            from: payer_token_account.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: payee_token_account.to_account_info(),
+                            authority: from_account.to_account_info(),
+                        },
+                    ),
+                    amount,
+                    mint.decimals,
+                )?;
+                Some(mint.key())
+            }
+            _ => return err!(ExecutorErrors::InvalidArguments),
+        };
+
+        emit!(RequestForExecutionEvent {
+            quoter_address: quote_header.quoter_address,
+            amt_paid: amount,
+            mint,
+            dst_chain,
+            dst_addr,
+            refund_addr,
+            signed_quote: signed_quote_bytes,
+            request_bytes,
+            relay_instructions,
+        });
 
         Ok(())
     }
 }
 
+fn validate_relay_instructions(relay_instructions: &[u8]) -> Result<()> {
+    match parse_relay_instructions(relay_instructions) {
+        Ok(_) => Ok(()),
+        // unknown types may be supported by relayers newer than this program, so they are only rejected in strict mode
+        #[cfg(not(feature = "strict-relay"))]
+        Err(executor_requests::relay_instructions::RelayParseError::UnsupportedType(_)) => Ok(()),
+        Err(_) => err!(ExecutorErrors::InvalidRelayInstructions),
+    }
+}
+
+/// Compares two byte slices without short-circuiting on the first differing byte.
+/// The addresses compared are not secret, but this keeps all address checks consistent and free of timing differences.
+fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 #[derive(Accounts)]
 pub struct RequestForExecution<'info> {
     #[account(mut)]
@@ -94,10 +215,18 @@ pub struct RequestForExecution<'info> {
     #[account(mut)]
     pub payee: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
+    /// The following accounts are only provided when paying in SPL tokens instead of lamports
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, token::mint = mint, token::authority = payer)]
+    pub payer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, token::mint = mint)]
+    pub payee_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RequestForExecutionArgs {
+    pub version: u8,
     pub amount: u64,
     pub dst_chain: u16,
     pub dst_addr: [u8; 32],
@@ -119,4 +248,97 @@ pub enum ExecutorErrors {
     QuoteExpired = 0x3,
     #[msg("QuotePayeeMismatch")]
     QuotePayeeMismatch = 0x4,
+    #[msg("InvalidRelayInstructions")]
+    InvalidRelayInstructions = 0x5,
+    #[msg("QuoteExpiresTooSoon")]
+    QuoteExpiresTooSoon = 0x6,
+    #[msg("InsufficientFunds")]
+    InsufficientFunds = 0x7,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use executor_requests::{
+        relay_instructions::make_gas_instruction,
+        request_for_execution::ExecutionRequestBuilder,
+        signed_quote::{signed_quote_len, SecretKey, SignedQuoteBuilder},
+        Request,
+    };
+
+    // tests/executor.ts submits these same bytes to check the program accepts the builder's output
+    static BUILDER_SIGNED_QUOTE: &str = "455130317e5f4552091a69125d5dfcb7b8c2659029395bdf22222222222222222222222222222222222222222222222222222222222222220001000200000000f48657000000000000002710000000003b9aca0700001d624add080000000625b3cb460099211220a81d23315257fee04537eb01d3ce22d9ce4bc502cd49774000804a0310b7f7afdc03da3e136fb8e5f713241cc4f0c0dc451394bc97b6a62ba546c4131c";
+
+    #[test]
+    fn test_execution_request_builder_matches_args() {
+        let request = Request::VaaV1 {
+            chain: 10002,
+            address: [0x11; 32],
+            sequence: 29,
+        };
+        let relay_instructions = make_gas_instruction(250_000, 5);
+        let args = RequestForExecutionArgs {
+            version: REQUEST_FOR_EXECUTION_ARGS_VERSION,
+            amount: 1000,
+            dst_chain: 2,
+            dst_addr: [0xaa; 32],
+            refund_addr: Pubkey::new_from_array([0xbb; 32]),
+            signed_quote_bytes: vec![0x01, 0x02],
+            request_bytes: request.encode(),
+            relay_instructions: relay_instructions.clone(),
+        };
+        let built = ExecutionRequestBuilder::new(2, [0xaa; 32])
+            .amount(1000)
+            .refund_addr([0xbb; 32])
+            .signed_quote_bytes(vec![0x01, 0x02])
+            .request(&request)
+            .relay_instruction(&relay_instructions)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(args.try_to_vec().unwrap(), built);
+    }
+
+    #[test]
+    fn test_signed_quote_builder_vector() {
+        let mut secret_key = [0u8; 32];
+        secret_key[31] = 1;
+        let quote = SignedQuoteBuilder::new(OUR_CHAIN, 2, 4102444800, [0x22; 32])
+            .base_fee(10000)
+            .dst_gas_price(1000000007)
+            .src_price(32308000000000)
+            .dst_price(6759000000000)
+            .sign(&SecretKey::parse(&secret_key).unwrap())
+            .build()
+            .unwrap();
+        let header = parse_signed_quote_header(&quote).unwrap();
+        assert_eq!(signed_quote_len(&header.prefix), Some(quote.len()));
+        let expected: Vec<u8> = (0..BUILDER_SIGNED_QUOTE.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&BUILDER_SIGNED_QUOTE[i..i + 2], 16).unwrap())
+            .collect();
+        assert_eq!(quote, expected);
+    }
+
+    #[test]
+    fn test_validate_gas_instruction() {
+        assert!(validate_relay_instructions(&make_gas_instruction(250_000, 0)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_truncated_instruction() {
+        assert!(validate_relay_instructions(&[0x01]).is_err());
+    }
+
+    #[cfg(not(feature = "strict-relay"))]
+    #[test]
+    fn test_validate_unknown_type_permissive() {
+        assert!(validate_relay_instructions(&[0xff]).is_ok());
+    }
+
+    #[cfg(feature = "strict-relay")]
+    #[test]
+    fn test_validate_unknown_type_strict() {
+        assert!(validate_relay_instructions(&[0xff]).is_err());
+    }
 }
@@ -14,6 +14,18 @@ describe("executor", () => {
 
   const program = anchor.workspace.Executor as Program<Executor>;
 
+  // the payee must differ from the payer and already exist, see "Pays the payee!"
+  const payee = new anchor.web3.Keypair().publicKey;
+
+  before(async () => {
+    const p = anchor.getProvider();
+    const tx = await p.connection.requestAirdrop(payee, 1_000_000);
+    await p.connection.confirmTransaction({
+      ...(await p.connection.getLatestBlockhash()),
+      signature: tx,
+    });
+  });
+
   const encodeSignedQuoteHeader = (
     prefix: string,
     quoterAddress: string,
@@ -30,13 +42,104 @@ describe("executor", () => {
       .writeUint16(srcChain)
       .writeUint16(dstChain)
       .writeUint64(expiryTime)
-      .writeHex(additionalBytes || "00") // just for testing
+      .writeHex(additionalBytes || "00".repeat(32 + 65)) // just for testing
       .data();
 
+  // reads the cluster's unix timestamp, against which the program checks quote expiry
+  const getClusterTime = async () => {
+    const clock = await program.provider.connection.getAccountInfo(
+      anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      "processed",
+    );
+    return clock!.data.readBigInt64LE(32);
+  };
+
+  const TOKEN_PROGRAM_ID = new anchor.web3.PublicKey(
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+  );
+
+  // creates an account of `space` bytes owned by the token program and runs `data` against it
+  const initTokenProgramAccount = async (
+    account: anchor.web3.Keypair,
+    space: number,
+    keys: anchor.web3.AccountMeta[],
+    data: Buffer,
+  ) => {
+    const provider = program.provider as anchor.AnchorProvider;
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: provider.publicKey,
+          newAccountPubkey: account.publicKey,
+          lamports:
+            await provider.connection.getMinimumBalanceForRentExemption(space),
+          space,
+          programId: TOKEN_PROGRAM_ID,
+        }),
+        new anchor.web3.TransactionInstruction({
+          programId: TOKEN_PROGRAM_ID,
+          keys: [
+            { pubkey: account.publicKey, isSigner: false, isWritable: true },
+            ...keys,
+          ],
+          data,
+        }),
+      ),
+      [account],
+    );
+  };
+
+  // creates a new mint and a token account for the provider holding some of it
+  const createFundedMint = async () => {
+    const provider = program.provider as anchor.AnchorProvider;
+    const mint = new anchor.web3.Keypair();
+    // InitializeMint2 with 6 decimals, the provider as mint authority and no freeze authority
+    await initTokenProgramAccount(
+      mint,
+      82,
+      [],
+      Buffer.concat([
+        Buffer.from([20, 6]),
+        provider.publicKey.toBuffer(),
+        Buffer.alloc(33),
+      ]),
+    );
+    const createTokenAccount = async (owner: anchor.web3.PublicKey) => {
+      const tokenAccount = new anchor.web3.Keypair();
+      // InitializeAccount3
+      await initTokenProgramAccount(
+        tokenAccount,
+        165,
+        [{ pubkey: mint.publicKey, isSigner: false, isWritable: false }],
+        Buffer.concat([Buffer.from([18]), owner.toBuffer()]),
+      );
+      return tokenAccount.publicKey;
+    };
+    const payerTokenAccount = await createTokenAccount(provider.publicKey);
+    const amount = Buffer.alloc(8);
+    amount.writeBigUInt64LE(BigInt(1_000_000));
+    // MintTo
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        new anchor.web3.TransactionInstruction({
+          programId: TOKEN_PROGRAM_ID,
+          keys: [
+            { pubkey: mint.publicKey, isSigner: false, isWritable: true },
+            { pubkey: payerTokenAccount, isSigner: false, isWritable: true },
+            { pubkey: provider.publicKey, isSigner: true, isWritable: false },
+          ],
+          data: Buffer.concat([Buffer.from([7]), amount]),
+        }),
+      ),
+    );
+    return { mint: mint.publicKey, payerTokenAccount, createTokenAccount };
+  };
+
   it("Requests execution!", async () => {
     await expect(
       program.methods
         .requestForExecution({
+          version: 0,
           amount: new BN(1),
           dstChain: 2,
           dstAddr: [
@@ -50,7 +153,7 @@ describe("executor", () => {
             encodeSignedQuoteHeader(
               "EQ01",
               "0x0000000000000000000000000000000000000000",
-              program.provider.publicKey!,
+              payee,
               1,
               2,
               BigInt(Date.now() + 1_000_000) / BigInt(1000),
@@ -60,7 +163,7 @@ describe("executor", () => {
           relayInstructions: Buffer.from("", "hex"),
         })
         .accounts({
-          payee: program.provider.publicKey!,
+          payee,
         })
         .rpc(),
     ).to.be.fulfilled;
@@ -70,6 +173,7 @@ describe("executor", () => {
     await expect(
       program.methods
         .requestForExecution({
+          version: 0,
           amount: new BN(1),
           dstChain: 2,
           dstAddr: [
@@ -83,7 +187,7 @@ describe("executor", () => {
             encodeSignedQuoteHeader(
               "EQ01",
               "0x0000000000000000000000000000000000000000",
-              program.provider.publicKey!,
+              payee,
               1,
               2,
               BigInt(Date.now() + 1_000_000) / BigInt(1000),
@@ -100,7 +204,7 @@ describe("executor", () => {
           ),
         })
         .accounts({
-          payee: program.provider.publicKey!,
+          payee,
         })
         .rpc(),
     ).to.be.fulfilled;
@@ -123,6 +227,7 @@ describe("executor", () => {
     const payment = 1000;
     await program.methods
       .requestForExecution({
+        version: 0,
         amount: new BN(payment),
         dstChain: 2,
         dstAddr: [
@@ -157,6 +262,7 @@ describe("executor", () => {
     await expect(
       program.methods
         .requestForExecution({
+          version: 0,
           amount: new BN(1),
           dstChain: 2,
           dstAddr: [
@@ -170,7 +276,7 @@ describe("executor", () => {
             encodeSignedQuoteHeader(
               "EQ01",
               "0x0000000000000000000000000000000000000000",
-              program.provider.publicKey!,
+              payee,
               2,
               2,
               BigInt(Date.now() + 1_000_000) / BigInt(1000),
@@ -180,7 +286,7 @@ describe("executor", () => {
           relayInstructions: Buffer.from("", "hex"),
         })
         .accounts({
-          payee: program.provider.publicKey!,
+          payee,
         })
         .rpc(),
     ).to.be.rejectedWith(
@@ -192,6 +298,7 @@ describe("executor", () => {
     await expect(
       program.methods
         .requestForExecution({
+          version: 0,
           amount: new BN(1),
           dstChain: 2,
           dstAddr: [
@@ -205,7 +312,7 @@ describe("executor", () => {
             encodeSignedQuoteHeader(
               "EQ01",
               "0x0000000000000000000000000000000000000000",
-              program.provider.publicKey!,
+              payee,
               1,
               4,
               BigInt(Date.now() + 1_000_000) / BigInt(1000),
@@ -215,7 +322,7 @@ describe("executor", () => {
           relayInstructions: Buffer.from("", "hex"),
         })
         .accounts({
-          payee: program.provider.publicKey!,
+          payee,
         })
         .rpc(),
     ).to.be.rejectedWith(
@@ -227,6 +334,7 @@ describe("executor", () => {
     await expect(
       program.methods
         .requestForExecution({
+          version: 0,
           amount: new BN(1),
           dstChain: 2,
           dstAddr: [
@@ -240,7 +348,7 @@ describe("executor", () => {
             encodeSignedQuoteHeader(
               "EQ01",
               "0x0000000000000000000000000000000000000000",
-              program.provider.publicKey!,
+              payee,
               1,
               2,
               BigInt(Date.now() - 1_000_000) / BigInt(1000),
@@ -250,7 +358,7 @@ describe("executor", () => {
           relayInstructions: Buffer.from("", "hex"),
         })
         .accounts({
-          payee: program.provider.publicKey!,
+          payee,
         })
         .rpc(),
     ).to.be.rejectedWith(
@@ -262,6 +370,7 @@ describe("executor", () => {
     await expect(
       program.methods
         .requestForExecution({
+          version: 0,
           amount: new BN(1),
           dstChain: 2,
           dstAddr: [
@@ -275,7 +384,7 @@ describe("executor", () => {
             encodeSignedQuoteHeader(
               "EQ01",
               "0x0000000000000000000000000000000000000000",
-              program.provider.publicKey!,
+              payee,
               1,
               2,
               BigInt(Date.now() + 1_000_000) / BigInt(1000),
@@ -292,4 +401,637 @@ describe("executor", () => {
       "Error Code: QuotePayeeMismatch. Error Number: 6004. Error Message: QuotePayeeMismatch.",
     );
   });
+
+  it("Reverts with invalid relay instructions!", async () => {
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("01", "hex"),
+        })
+        .accounts({
+          payee,
+        })
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: InvalidRelayInstructions. Error Number: 6005. Error Message: InvalidRelayInstructions.",
+    );
+  });
+
+  it("Reverts with too long relay instructions!", async () => {
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from(
+            "01000000000000000000000000000f424000000000000000000000000000000000".repeat(
+              16,
+            ),
+            "hex",
+          ),
+        })
+        .accounts({
+          payee,
+        })
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: InvalidRelayInstructions. Error Number: 6005. Error Message: InvalidRelayInstructions.",
+    );
+  });
+
+  it("Reverts with too long request bytes!", async () => {
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+            ),
+          ),
+          requestBytes: Buffer.alloc(513),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accounts({
+          payee,
+        })
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: InvalidArguments. Error Number: 6000. Error Message: InvalidArguments.",
+    );
+  });
+
+  it("Reverts with unknown args version!", async () => {
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 1,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accounts({
+          payee,
+        })
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: InvalidArguments. Error Number: 6000. Error Message: InvalidArguments.",
+    );
+  });
+
+  it("Reverts with amount above the cap!", async () => {
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(100 * anchor.web3.LAMPORTS_PER_SOL + 1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accounts({
+          payee,
+        })
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: InvalidArguments. Error Number: 6000. Error Message: InvalidArguments.",
+    );
+  });
+
+  it("Reverts with trailing signed quote bytes!", async () => {
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+              "00".repeat(32 + 65 + 1),
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accounts({
+          payee,
+        })
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: InvalidArguments. Error Number: 6000. Error Message: InvalidArguments.",
+    );
+  });
+
+  it("Reverts with payer as payee!", async () => {
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              program.provider.publicKey!,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accounts({
+          payee: program.provider.publicKey!,
+        })
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: InvalidArguments. Error Number: 6000. Error Message: InvalidArguments.",
+    );
+  });
+
+  it("Reverts with insufficient funds!", async () => {
+    const payer = new anchor.web3.Keypair();
+    const initialBalance = 1_000_000;
+    {
+      const p = anchor.getProvider();
+      const tx = await p.connection.requestAirdrop(
+        payer.publicKey,
+        initialBalance,
+      );
+      await p.connection.confirmTransaction({
+        ...(await p.connection.getLatestBlockhash()),
+        signature: tx,
+      });
+    }
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(initialBalance),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: payer.publicKey,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accountsPartial({
+          payer: payer.publicKey,
+          payee,
+        })
+        .signers([payer])
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: InsufficientFunds. Error Number: 6007. Error Message: InsufficientFunds.",
+    );
+  });
+
+  it("Pays the payee in tokens!", async () => {
+    const { mint, payerTokenAccount, createTokenAccount } =
+      await createFundedMint();
+    const payeeTokenAccount = await createTokenAccount(payee);
+    const payment = 1000;
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(payment),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accountsPartial({
+          payee,
+          mint,
+          payerTokenAccount,
+          payeeTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc(),
+    ).to.be.fulfilled;
+
+    expect(
+      (
+        await program.provider.connection.getTokenAccountBalance(
+          payeeTokenAccount,
+          "processed",
+        )
+      ).value.amount,
+    ).to.equal(payment.toString());
+  });
+
+  it("Reverts with only some token accounts!", async () => {
+    const { mint, payerTokenAccount } = await createFundedMint();
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accountsPartial({
+          payee,
+          mint,
+          payerTokenAccount,
+          payeeTokenAccount: null,
+          tokenProgram: null,
+        })
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: InvalidArguments. Error Number: 6000. Error Message: InvalidArguments.",
+    );
+  });
+
+  it("Reverts with payee token account owner mismatch!", async () => {
+    const { mint, payerTokenAccount, createTokenAccount } =
+      await createFundedMint();
+    const payeeTokenAccount = await createTokenAccount(
+      new anchor.web3.Keypair().publicKey,
+    );
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accountsPartial({
+          payee,
+          mint,
+          payerTokenAccount,
+          payeeTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: QuotePayeeMismatch. Error Number: 6004. Error Message: QuotePayeeMismatch.",
+    );
+  });
+
+  it("Reverts with quote expiring at the cluster time!", async () => {
+    const expiryTime = await getClusterTime();
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              expiryTime,
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accountsPartial({
+          payee,
+        })
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: QuoteExpired. Error Number: 6003. Error Message: QuoteExpired.",
+    );
+  });
+
+  it("Reverts with quote expiring too soon!", async () => {
+    // halfway into the program's 20 second minimum validity, so clock drift can't make it pass or expire
+    const expiryTime = (await getClusterTime()) + BigInt(10);
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ01",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              expiryTime,
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accountsPartial({
+          payee,
+        })
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: QuoteExpiresTooSoon. Error Number: 6006. Error Message: QuoteExpiresTooSoon.",
+    );
+  });
+
+  it("Requests execution with max length signed quote!", async () => {
+    // quotes of an unknown version are not length checked, so only the cap applies
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ02",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+              "00".repeat(512 - 68),
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accountsPartial({
+          payee,
+        })
+        .rpc(),
+    ).to.be.fulfilled;
+  });
+
+  it("Reverts with too long signed quote!", async () => {
+    // quotes of an unknown version are not length checked, so only the cap applies
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            encodeSignedQuoteHeader(
+              "EQ02",
+              "0x0000000000000000000000000000000000000000",
+              payee,
+              1,
+              2,
+              BigInt(Date.now() + 1_000_000) / BigInt(1000),
+              "00".repeat(513 - 68),
+            ),
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accountsPartial({
+          payee,
+        })
+        .rpc(),
+    ).to.be.rejectedWith(
+      "Error Code: InvalidArguments. Error Number: 6000. Error Message: InvalidArguments.",
+    );
+  });
+
+  it("Requests execution with a signed quote from the SignedQuoteBuilder!", async () => {
+    // the output of SignedQuoteBuilder in test_signed_quote_builder_vector, see programs/executor/src/lib.rs
+    const builderPayee = new anchor.web3.PublicKey(Buffer.alloc(32, 0x22));
+    {
+      const p = anchor.getProvider();
+      const tx = await p.connection.requestAirdrop(builderPayee, 1_000_000);
+      await p.connection.confirmTransaction({
+        ...(await p.connection.getLatestBlockhash()),
+        signature: tx,
+      });
+    }
+    await expect(
+      program.methods
+        .requestForExecution({
+          version: 0,
+          amount: new BN(1),
+          dstChain: 2,
+          dstAddr: [
+            ...Buffer.from(
+              "0000000000000000000000000000000000000000000000000000000000000000",
+              "hex",
+            ),
+          ],
+          refundAddr: program.provider.publicKey!,
+          signedQuoteBytes: Buffer.from(
+            "455130317e5f4552091a69125d5dfcb7b8c2659029395bdf22222222222222222222222222222222222222222222222222222222222222220001000200000000f48657000000000000002710000000003b9aca0700001d624add080000000625b3cb460099211220a81d23315257fee04537eb01d3ce22d9ce4bc502cd49774000804a0310b7f7afdc03da3e136fb8e5f713241cc4f0c0dc451394bc97b6a62ba546c4131c",
+            "hex",
+          ),
+          requestBytes: Buffer.from("", "hex"),
+          relayInstructions: Buffer.from("", "hex"),
+        })
+        .accounts({
+          payee: builderPayee,
+        })
+        .rpc(),
+    ).to.be.fulfilled;
+  });
 });